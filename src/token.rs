@@ -2,45 +2,253 @@
 ///
 /// We retain jsonwebtoken's default 60-second fudge factor to allow for clock skew.
 
+use std::fmt;
 use std::time::{Duration, UNIX_EPOCH, SystemTime};
 use jsonwebtoken as jwt;
 use serde::{Serialize, Deserialize, de::DeserializeOwned};
-use lazy_static::lazy_static;
+use uuid::Uuid;
+
+/// Crate-level error type, so callers can branch on "session expired" vs "bad signature"
+/// vs "token from the future" without reaching into `jsonwebtoken`'s own error kinds.
+#[derive(Debug)]
+pub enum SessionError {
+    /// The token's `exp` has passed.
+    Expired,
+    /// The token's `nbf` is still in the future.
+    NotYetValid,
+    /// The signature didn't verify against the given key. (A token signed with an algorithm
+    /// other than the expected one is reported as `Malformed`.)
+    InvalidSignature,
+    /// The token's `iss` didn't match the one required by `Config`.
+    InvalidIssuer,
+    /// The token's `aud` didn't match the one required by `Config`.
+    InvalidAudience,
+    /// The token was missing a required claim, or wasn't well-formed JWT in some other way.
+    Malformed,
+    /// Signing the token failed.
+    EncodeFailed,
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SessionError::Expired => write!(f, "token has expired"),
+            SessionError::NotYetValid => write!(f, "token is not yet valid"),
+            SessionError::InvalidSignature => write!(f, "token signature is invalid"),
+            SessionError::InvalidIssuer => write!(f, "token issuer does not match"),
+            SessionError::InvalidAudience => write!(f, "token audience does not match"),
+            SessionError::Malformed => write!(f, "token is malformed"),
+            SessionError::EncodeFailed => write!(f, "failed to encode token"),
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+// Note: `SessionError::InvalidIssuer`/`InvalidAudience` are never produced here --
+// `decode_claims` checks `iss`/`aud` directly against the deserialized `Claims<T>` rather
+// than via `Validation::set_issuer`/`set_audience`, so `jsonwebtoken` itself never raises
+// `ErrorKind::InvalidIssuer`/`InvalidAudience` in this codebase. Don't "simplify" the manual
+// check in `decode_claims` on the assumption that this impl already covers it.
+impl From<jwt::errors::Error> for SessionError {
+    fn from(err: jwt::errors::Error) -> Self {
+        match err.kind() {
+            jwt::errors::ErrorKind::ExpiredSignature => SessionError::Expired,
+            jwt::errors::ErrorKind::ImmatureSignature => SessionError::NotYetValid,
+            jwt::errors::ErrorKind::InvalidSignature => SessionError::InvalidSignature,
+            _ => SessionError::Malformed,
+        }
+    }
+}
 
 #[derive(Serialize, Deserialize)]
 struct Claims<T> {
-    exp: u64,
+    // absent for tokens minted with `encode_forever`, which never expire
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exp: Option<u64>,
     nbf: u64,
+    iat: u64,
+    // unique per token, so a host application can check it against a revocation/denylist store
+    jti: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    iss: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    aud: Option<String>,
     inner: T,
 }
 
-// Internal function which allows specifying the current time (meaning we can test it)
-fn encode_internal<T: Serialize>(inner: T, length: Duration, ek: &jwt::EncodingKey, current_time_since_epoch: Duration) -> String {
+/// Full claim metadata alongside `inner`, for callers that need `jti` (to check against a
+/// revocation/denylist store) or `iat` (to enforce "log out all sessions issued before time
+/// X" policies) -- information the plain `decode*` functions discard.
+pub struct TokenMetadata<T> {
+    pub inner: T,
+    pub iat: u64,
+    pub jti: String,
+    pub nbf: u64,
+    pub exp: Option<u64>,
+}
+
+impl<T> From<Claims<T>> for TokenMetadata<T> {
+    fn from(claims: Claims<T>) -> Self {
+        TokenMetadata {
+            inner: claims.inner,
+            iat: claims.iat,
+            jti: claims.jti,
+            nbf: claims.nbf,
+            exp: claims.exp,
+        }
+    }
+}
+
+// Knobs beyond the bare `inner`/`length` pair: which algorithm to sign/verify with, and
+// the issuer/audience to stamp or require. `alg` defaults to the original HS256-everywhere
+// behaviour; set it explicitly when using an RSA/ECDSA key pair so a token signed with an
+// unexpected algorithm is rejected rather than silently accepted.
+pub struct Config {
+    pub alg: jwt::Algorithm,
+    pub iss: Option<String>,
+    pub aud: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            alg: jwt::Algorithm::HS256,
+            iss: None,
+            aud: None,
+        }
+    }
+}
+
+// Internal function which allows specifying the current time (meaning we can test it).
+// `length` of `None` means the token never expires (see `encode_forever`).
+fn encode_internal<T: Serialize>(inner: T, length: Option<Duration>, ek: &jwt::EncodingKey, current_time_since_epoch: Duration, config: &Config) -> Result<String, SessionError> {
     let claims = Claims {
         nbf: current_time_since_epoch.as_secs(),
-        exp: (current_time_since_epoch + length).as_secs(),
+        iat: current_time_since_epoch.as_secs(),
+        jti: Uuid::new_v4().to_string(),
+        exp: length.map(|length| (current_time_since_epoch + length).as_secs()),
+        iss: config.iss.clone(),
+        aud: config.aud.clone(),
         inner: inner,
     };
 
-    jwt::encode(&jwt::Header::default(), &claims, ek).expect("jwt encoding failed")
+    jwt::encode(&jwt::Header::new(config.alg), &claims, ek).map_err(|_| SessionError::EncodeFailed)
+}
+
+pub fn encode<T: Serialize>(inner: T, length: Duration, ek: &jwt::EncodingKey) -> Result<String, SessionError> {
+    encode_internal(inner, Some(length), ek, SystemTime::now().duration_since(UNIX_EPOCH).expect("pretty sure rust hasn't been invented yet..."), &Config::default())
+}
+
+// Like `encode`, but lets the caller pin the signing algorithm (e.g. for an RSA/ECDSA key
+// pair) and stamp an issuer/audience that `decode_with_config` can require on the way back
+// in, so a token minted for one service can't be replayed against another that happens to
+// share the same secret.
+pub fn encode_with_config<T: Serialize>(inner: T, length: Duration, ek: &jwt::EncodingKey, config: &Config) -> Result<String, SessionError> {
+    encode_internal(inner, Some(length), ek, SystemTime::now().duration_since(UNIX_EPOCH).expect("pretty sure rust hasn't been invented yet..."), config)
 }
 
-pub fn encode<T: Serialize>(inner: T, length: Duration, ek: &jwt::EncodingKey) -> String {
-    encode_internal(inner, length, ek, SystemTime::now().duration_since(UNIX_EPOCH).expect("pretty sure rust hasn't been invented yet..."))
+// Mints a token with no `exp` claim at all, so it never expires. This is for the rare
+// caller that genuinely wants that (service-to-service API keys, long-lived admin
+// sessions) — an explicit opt-in rather than something achieved by passing an absurdly
+// large `Duration` to `encode`. `nbf` is still set and still enforced on decode.
+pub fn encode_forever<T: Serialize>(inner: T, ek: &jwt::EncodingKey, config: &Config) -> Result<String, SessionError> {
+    encode_internal(inner, None, ek, SystemTime::now().duration_since(UNIX_EPOCH).expect("pretty sure rust hasn't been invented yet..."), config)
 }
 
-lazy_static! {
-    static ref VALIDATION: jwt::Validation = {
-        let mut validation = jwt::Validation::default();
+// Validation shared by every decode entry point; `Validation::new` pins `algorithms` to
+// exactly the one given, so a token signed with an unexpected algorithm is rejected outright.
+// `require_exp` is false for `decode_forever`, which relaxes the `exp` requirement to accept
+// the non-expiring tokens `encode_forever` mints, while `nbf` is always enforced.
+fn base_validation(alg: jwt::Algorithm, require_exp: bool) -> jwt::Validation {
+    let mut validation = jwt::Validation::new(alg);
+    if require_exp {
         validation.set_required_spec_claims(&["nbf", "exp"]);
-        validation.validate_exp = true;
-        validation.validate_nbf = true;
-        validation
-    };
+    } else {
+        validation.set_required_spec_claims(&["nbf"]);
+    }
+    validation.validate_exp = require_exp;
+    validation.validate_nbf = true;
+    validation
+}
+
+pub fn decode<'de, T: DeserializeOwned>(token: &str, dk: &jwt::DecodingKey) -> Result<T, SessionError> {
+    jwt::decode::<Claims<T>>(token, dk, &base_validation(jwt::Algorithm::HS256, true)).map(|x| x.claims.inner).map_err(SessionError::from)
 }
 
-pub fn decode<'de, T: DeserializeOwned>(token: &str, dk: &jwt::DecodingKey) -> Result<T, jwt::errors::Error> {
-    jwt::decode::<Claims<T>>(token, dk, &VALIDATION).map(|x| x.claims.inner)
+// Like `decode`, but verifies against the algorithm in `config` and, when set, rejects
+// tokens whose `iss`/`aud` claims don't match (a missing claim is treated as a mismatch),
+// returning `SessionError::InvalidIssuer`/`InvalidAudience` so a token can't be replayed
+// against the wrong service or accepted under the wrong key type.
+pub fn decode_with_config<'de, T: DeserializeOwned>(token: &str, dk: &jwt::DecodingKey, config: &Config) -> Result<T, SessionError> {
+    decode_claims::<T>(token, dk, config, true).map(|claims| claims.inner)
+}
+
+// Like `decode_with_config`, but returns the full claim metadata (`iat`, `jti`, `nbf`, `exp`)
+// alongside `inner` instead of discarding it, so a host application can check `jti` against a
+// denylist or enforce an `iat` cutoff for forced logout.
+pub fn decode_with_metadata<'de, T: DeserializeOwned>(token: &str, dk: &jwt::DecodingKey, config: &Config) -> Result<TokenMetadata<T>, SessionError> {
+    decode_claims::<T>(token, dk, config, true).map(TokenMetadata::from)
+}
+
+// Shared by every decode entry point except plain `decode`: `refresh` needs `exp` to know
+// how much of a token's lifetime is left. When `require_exp` is false, an `exp` claim is not
+// required, and a token that carries one anyway is rejected as `SessionError::Malformed` --
+// "forever" decoding is for tokens that were actually minted without an `exp`, not a way to
+// bypass expiry on an ordinary token.
+fn decode_claims<T: DeserializeOwned>(token: &str, dk: &jwt::DecodingKey, config: &Config, require_exp: bool) -> Result<Claims<T>, SessionError> {
+    let validation = base_validation(config.alg, require_exp);
+    let claims = jwt::decode::<Claims<T>>(token, dk, &validation).map(|x| x.claims).map_err(SessionError::from)?;
+
+    if !require_exp && claims.exp.is_some() {
+        return Err(SessionError::Malformed);
+    }
+
+    // Checked against the already-deserialized `Claims<T>` fields, rather than via
+    // `Validation::set_issuer`/`set_audience`, so a missing claim is unambiguously a mismatch
+    // (`jsonwebtoken` only checks a claim against `Validation` when it's present in the token)
+    // and so the iss-then-aud check order is deterministic rather than depending on
+    // `Validation::required_spec_claims`'s internal `HashSet` iteration order.
+    if let Some(expected_iss) = &config.iss {
+        if claims.iss.as_deref() != Some(expected_iss.as_str()) {
+            return Err(SessionError::InvalidIssuer);
+        }
+    }
+    if let Some(expected_aud) = &config.aud {
+        if claims.aud.as_deref() != Some(expected_aud.as_str()) {
+            return Err(SessionError::InvalidAudience);
+        }
+    }
+
+    Ok(claims)
+}
+
+// Decodes a token minted by `encode_forever`: doesn't require `exp`, and rejects the token
+// as malformed if it carries one anyway (ordinary expiring tokens must go through `decode`/
+// `decode_with_config`, where expiry is actually enforced). `nbf` and (when set) `iss`/`aud`
+// are still enforced.
+pub fn decode_forever<'de, T: DeserializeOwned>(token: &str, dk: &jwt::DecodingKey, config: &Config) -> Result<T, SessionError> {
+    decode_claims::<T>(token, dk, config, false).map(|claims| claims.inner)
+}
+
+// Internal function which allows specifying the current time (meaning we can test it)
+fn refresh_internal<T: Serialize + DeserializeOwned>(token: &str, length: Duration, threshold: Duration, ek: &jwt::EncodingKey, dk: &jwt::DecodingKey, config: &Config, current_time_since_epoch: Duration) -> Result<String, SessionError> {
+    let claims = decode_claims::<T>(token, dk, config, true)?;
+
+    let remaining = claims.exp.map(|exp| exp.saturating_sub(current_time_since_epoch.as_secs()));
+    match remaining {
+        Some(remaining) if remaining < threshold.as_secs() => encode_internal(claims.inner, Some(length), ek, current_time_since_epoch, config),
+        _ => Ok(token.to_string()),
+    }
+}
+
+// Keeps a web session alive without forcing re-auth at a hard cutoff: decodes a currently-valid
+// token and, if less than `threshold` of its lifetime remains, re-issues it with a fresh
+// `nbf`/`exp` window of `length`. Otherwise the token is returned unchanged. Errors (expired,
+// bad signature, etc.) propagate from the initial decode rather than minting a fresh token for
+// something that was never valid.
+pub fn refresh<T: Serialize + DeserializeOwned>(token: &str, length: Duration, threshold: Duration, ek: &jwt::EncodingKey, dk: &jwt::DecodingKey, config: &Config) -> Result<String, SessionError> {
+    refresh_internal::<T>(token, length, threshold, ek, dk, config, SystemTime::now().duration_since(UNIX_EPOCH).expect("pretty sure rust hasn't been invented yet..."))
 }
 
 
@@ -58,13 +266,167 @@ mod test {
 
         let now: Duration = SystemTime::now().duration_since(UNIX_EPOCH).expect("pretty sure rust hasn't been invented yet...");
 
-        assert_eq!(decode::<String>(&encode("superadmin".to_string(), expiry, &ek), &dk).expect("currently-valid token should be accepted"), "superadmin", "token data should roundtrip");
+        assert_eq!(decode::<String>(&encode("superadmin".to_string(), expiry, &ek).expect("token should encode"), &dk).expect("currently-valid token should be accepted"), "superadmin", "token data should roundtrip");
         // or, equivalently...
-        assert_eq!(decode::<String>(&encode_internal("superadmin".to_string(), expiry, &ek, now), &dk).expect("currently-valid token should be accepted"), "superadmin", "token data should roundtrip");
+        assert_eq!(decode::<String>(&encode_internal("superadmin".to_string(), Some(expiry), &ek, now, &Config::default()).expect("token should encode"), &dk).expect("currently-valid token should be accepted"), "superadmin", "token data should roundtrip");
 
         // created 10 minutes ago, so should have expired 5 minutes ago
-        assert!(decode::<String>(&encode_internal("superadmin".to_string(), expiry, &ek, now - Duration::from_secs(10*60)), &dk).is_err(), "expired token should not be accepted");
+        assert!(decode::<String>(&encode_internal("superadmin".to_string(), Some(expiry), &ek, now - Duration::from_secs(10*60), &Config::default()).expect("token should encode"), &dk).is_err(), "expired token should not be accepted");
         // created in 5 minutes, so shouldn't be valid until then
-        assert!(decode::<String>(&encode_internal("superadmin".to_string(), expiry, &ek, now + Duration::from_secs(5*60)), &dk).is_err(), "future token should not be accepted");
+        assert!(decode::<String>(&encode_internal("superadmin".to_string(), Some(expiry), &ek, now + Duration::from_secs(5*60), &Config::default()).expect("token should encode"), &dk).is_err(), "future token should not be accepted");
+    }
+
+    #[test]
+    fn test_issuer_audience() {
+        let secret = b"secret";
+        let ek = jwt::EncodingKey::from_secret(secret);
+        let dk = jwt::DecodingKey::from_secret(secret);
+
+        let expiry = Duration::from_secs(300);
+
+        let config = Config {
+            alg: jwt::Algorithm::HS256,
+            iss: Some("websessn".to_string()),
+            aud: Some("my-service".to_string()),
+        };
+
+        let token = encode_with_config("superadmin".to_string(), expiry, &ek, &config).expect("token should encode");
+
+        assert_eq!(decode_with_config::<String>(&token, &dk, &config).expect("token with matching issuer/audience should be accepted"), "superadmin", "token data should roundtrip");
+
+        let wrong_issuer = Config { iss: Some("someone-else".to_string()), ..Config::default() };
+        assert!(matches!(decode_with_config::<String>(&token, &dk, &wrong_issuer), Err(SessionError::InvalidIssuer)), "token with the wrong issuer should report SessionError::InvalidIssuer");
+
+        let wrong_audience = Config { aud: Some("someone-else".to_string()), ..Config::default() };
+        assert!(matches!(decode_with_config::<String>(&token, &dk, &wrong_audience), Err(SessionError::InvalidAudience)), "token with the wrong audience should report SessionError::InvalidAudience");
+
+        // tokens minted without iss/aud should still pass through `decode`, which doesn't require them
+        assert_eq!(decode::<String>(&encode("superadmin".to_string(), expiry, &ek).expect("token should encode"), &dk).expect("currently-valid token should be accepted"), "superadmin", "token data should roundtrip");
+
+        // ...but a token minted without an iss/aud claim at all must not satisfy a decoder
+        // that requires one -- a missing claim is a mismatch, not a free pass
+        let no_iss_aud = encode("superadmin".to_string(), expiry, &ek).expect("token should encode");
+        // iss is checked before aud, so a token missing both reports InvalidIssuer first
+        assert!(matches!(decode_with_config::<String>(&no_iss_aud, &dk, &config), Err(SessionError::InvalidIssuer)), "token with no issuer claim should report SessionError::InvalidIssuer, not a generic Malformed");
+
+        let iss_only = Config { alg: jwt::Algorithm::HS256, iss: Some("websessn".to_string()), aud: None };
+        let missing_aud_only = encode_with_config("superadmin".to_string(), expiry, &ek, &iss_only).expect("token should encode");
+        assert!(matches!(decode_with_config::<String>(&missing_aud_only, &dk, &config), Err(SessionError::InvalidAudience)), "token with no audience claim should report SessionError::InvalidAudience, not a generic Malformed");
+    }
+
+    #[test]
+    fn test_algorithm_pinning() {
+        let secret = b"secret";
+        let ek = jwt::EncodingKey::from_secret(secret);
+        let dk = jwt::DecodingKey::from_secret(secret);
+
+        let expiry = Duration::from_secs(300);
+
+        // a token signed with HS384 should not verify against a decoder pinned to HS256, even
+        // though both are keyed-hash algorithms over the same secret
+        let hs384 = Config { alg: jwt::Algorithm::HS384, ..Config::default() };
+        let token = encode_with_config("superadmin".to_string(), expiry, &ek, &hs384).expect("token should encode");
+        assert!(decode::<String>(&token, &dk).is_err(), "token signed with an unexpected algorithm should not be accepted");
+        assert_eq!(decode_with_config::<String>(&token, &dk, &hs384).expect("token signed with the expected algorithm should be accepted"), "superadmin", "token data should roundtrip");
+    }
+
+    #[test]
+    fn test_forever_token() {
+        let secret = b"secret";
+        let ek = jwt::EncodingKey::from_secret(secret);
+        let dk = jwt::DecodingKey::from_secret(secret);
+
+        let config = Config::default();
+        let token = encode_forever("superadmin".to_string(), &ek, &config).expect("token should encode");
+
+        assert_eq!(decode_forever::<String>(&token, &dk, &config).expect("non-expiring token should be accepted"), "superadmin", "token data should roundtrip");
+        // a non-expiring token carries no `exp` claim at all, so the ordinary `decode`, which
+        // requires one, should reject it
+        assert!(decode::<String>(&token, &dk).is_err(), "non-expiring token should not be accepted by a decoder that requires exp");
+    }
+
+    #[test]
+    fn test_forever_token_does_not_bypass_expiry() {
+        let secret = b"secret";
+        let ek = jwt::EncodingKey::from_secret(secret);
+        let dk = jwt::DecodingKey::from_secret(secret);
+
+        let config = Config::default();
+        let expiry = Duration::from_secs(300);
+        let now: Duration = SystemTime::now().duration_since(UNIX_EPOCH).expect("pretty sure rust hasn't been invented yet...");
+
+        // an ordinary token, minted with `exp` 20 minutes ago, that expired 15 minutes ago
+        let expired = encode_internal("superadmin".to_string(), Some(expiry), &ek, now - Duration::from_secs(20*60), &config).expect("token should encode");
+
+        assert!(decode_forever::<String>(&expired, &dk, &config).is_err(), "an expired ordinary token should not be accepted by decode_forever just because it skips exp validation");
+    }
+
+    #[test]
+    fn test_session_error_variants() {
+        let secret = b"secret";
+        let ek = jwt::EncodingKey::from_secret(secret);
+        let dk = jwt::DecodingKey::from_secret(secret);
+        let wrong_dk = jwt::DecodingKey::from_secret(b"wrong secret");
+
+        let expiry = Duration::from_secs(300);
+        let now: Duration = SystemTime::now().duration_since(UNIX_EPOCH).expect("pretty sure rust hasn't been invented yet...");
+
+        let expired = encode_internal("superadmin".to_string(), Some(expiry), &ek, now - Duration::from_secs(10*60), &Config::default()).expect("token should encode");
+        assert!(matches!(decode::<String>(&expired, &dk), Err(SessionError::Expired)), "expired token should report SessionError::Expired");
+
+        let not_yet_valid = encode_internal("superadmin".to_string(), Some(expiry), &ek, now + Duration::from_secs(5*60), &Config::default()).expect("token should encode");
+        assert!(matches!(decode::<String>(&not_yet_valid, &dk), Err(SessionError::NotYetValid)), "future token should report SessionError::NotYetValid");
+
+        let valid = encode("superadmin".to_string(), expiry, &ek).expect("token should encode");
+        assert!(matches!(decode::<String>(&valid, &wrong_dk), Err(SessionError::InvalidSignature)), "token verified with the wrong key should report SessionError::InvalidSignature");
+    }
+
+    #[test]
+    fn test_refresh() {
+        let secret = b"secret";
+        let ek = jwt::EncodingKey::from_secret(secret);
+        let dk = jwt::DecodingKey::from_secret(secret);
+
+        let config = Config::default();
+        let length = Duration::from_secs(300);
+        let threshold = Duration::from_secs(60);
+
+        let now: Duration = SystemTime::now().duration_since(UNIX_EPOCH).expect("pretty sure rust hasn't been invented yet...");
+
+        // minted 4 minutes 30 seconds ago, so only 30 seconds of its 5 minute lifetime remain:
+        // below the 60 second threshold, so it should be renewed with a fresh window
+        let near_expiry = encode_internal("superadmin".to_string(), Some(length), &ek, now - Duration::from_secs(270), &config).expect("token should encode");
+        let renewed = refresh_internal::<String>(&near_expiry, length, threshold, &ek, &dk, &config, now).expect("near-expiry token should be renewed");
+        assert_ne!(renewed, near_expiry, "a token below the threshold should be re-issued");
+        assert_eq!(decode_with_config::<String>(&renewed, &dk, &config).expect("renewed token should be valid"), "superadmin", "token data should survive a renewal");
+
+        // minted just now, so almost all of its lifetime remains: well above the threshold
+        let fresh = encode_internal("superadmin".to_string(), Some(length), &ek, now, &config).expect("token should encode");
+        let unchanged = refresh_internal::<String>(&fresh, length, threshold, &ek, &dk, &config, now).expect("fresh token should be accepted");
+        assert_eq!(unchanged, fresh, "a token well within its lifetime should be returned unchanged");
+
+        // minted 10 minutes ago, so already expired 5 minutes ago
+        let expired = encode_internal("superadmin".to_string(), Some(length), &ek, now - Duration::from_secs(10*60), &config).expect("token should encode");
+        assert!(refresh_internal::<String>(&expired, length, threshold, &ek, &dk, &config, now).is_err(), "an already-expired token should not be renewed");
+    }
+
+    #[test]
+    fn test_metadata() {
+        let secret = b"secret";
+        let ek = jwt::EncodingKey::from_secret(secret);
+        let dk = jwt::DecodingKey::from_secret(secret);
+
+        let config = Config::default();
+        let expiry = Duration::from_secs(300);
+
+        let token_a = encode_with_config("superadmin".to_string(), expiry, &ek, &config).expect("token should encode");
+        let token_b = encode_with_config("superadmin".to_string(), expiry, &ek, &config).expect("token should encode");
+
+        let meta_a = decode_with_metadata::<String>(&token_a, &dk, &config).expect("token should decode");
+        let meta_b = decode_with_metadata::<String>(&token_b, &dk, &config).expect("token should decode");
+
+        assert_eq!(meta_a.inner, "superadmin", "token data should roundtrip");
+        assert_ne!(meta_a.jti, meta_b.jti, "each minted token should get a distinct jti, so a denylist can target one without the other");
+        assert_eq!(meta_a.exp, Some(meta_a.iat + expiry.as_secs()), "exp should be iat plus the requested lifetime");
     }
 }